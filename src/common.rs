@@ -2,12 +2,13 @@ use std::{
     env,
     ffi::OsString,
     io::{BufRead, BufReader, ErrorKind},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{ExitStatus, Stdio},
 };
 
 use fs_extra::file::read_to_string;
 use lazy_static::lazy_static;
+use semver::{Version, VersionReq};
 use serde_json::{from_str, Value};
 
 use crate::command as CommandTool;
@@ -16,7 +17,7 @@ lazy_static! {
     pub static ref NVMD_PATH: PathBuf = get_nvmd_path();
     pub static ref VERSION: String = get_version();
     pub static ref DEFAULT_INSTALLTION_PATH: PathBuf = get_default_installtion_path();
-    pub static ref INSTALLTION_PATH: PathBuf = get_installtion_path();
+    pub static ref INSTALLTION_PATH: Vec<PathBuf> = get_installtion_path();
     pub static ref NPM_PREFIX: PathBuf = get_npm_prefix();
     pub static ref ENV_PATH: OsString = get_env_path(false);
     pub static ref BINARY_ENV_PATH: OsString = get_env_path(true);
@@ -25,12 +26,15 @@ lazy_static! {
 fn get_npm_prefix() -> PathBuf {
     let mut command = CommandTool::create_command("npm");
 
-    let child = command
+    let child = match command
         .env("PATH", ENV_PATH.clone())
         .args(["config", "get", "prefix"])
         .stdout(Stdio::piped())
         .spawn()
-        .expect("nvmd-desktop: get npm perfix error");
+    {
+        Err(_) => return PathBuf::from(""),
+        Ok(child) => child,
+    };
 
     let output = child.stdout.unwrap();
     let lines = BufReader::new(output).lines();
@@ -74,8 +78,23 @@ fn get_env_path(binary: bool) -> OsString {
     }
 }
 
-fn get_bin_path() -> OsString {
-    let mut nvmd_path = INSTALLTION_PATH.clone();
+pub(crate) fn get_bin_path() -> OsString {
+    for root in INSTALLTION_PATH.iter() {
+        let mut version_dir = root.clone();
+        version_dir.push(VERSION.clone());
+
+        if version_dir.is_dir() {
+            if cfg!(unix) {
+                version_dir.push("bin");
+            }
+
+            return version_dir.into_os_string();
+        }
+    }
+
+    // None of the configured roots have this version installed; fall back to
+    // the first root so callers still see a stable (if missing) path.
+    let mut nvmd_path = INSTALLTION_PATH[0].clone();
     nvmd_path.push(VERSION.clone());
 
     if cfg!(unix) {
@@ -85,7 +104,7 @@ fn get_bin_path() -> OsString {
     nvmd_path.into_os_string()
 }
 
-fn get_binary_bin_path() -> OsString {
+pub(crate) fn get_binary_bin_path() -> OsString {
     let mut nvmd_path = NPM_PREFIX.clone();
 
     if cfg!(unix) {
@@ -96,7 +115,12 @@ fn get_binary_bin_path() -> OsString {
 }
 
 // $HOME/.nvmd/setting.json -> directory
-fn get_installtion_path() -> PathBuf {
+//
+// `directory` may be a single string or a JSON array of strings. Each root is
+// searched in order when resolving a version, so users can keep installed
+// versions across several disks/volumes, or layer a read-only system-wide
+// store in front of a writable per-user one.
+fn get_installtion_path() -> Vec<PathBuf> {
     let mut setting_path = NVMD_PATH.clone();
     setting_path.push("setting.json");
 
@@ -106,22 +130,32 @@ fn get_installtion_path() -> PathBuf {
     };
 
     if setting_content.is_empty() {
-        return DEFAULT_INSTALLTION_PATH.clone();
+        return vec![DEFAULT_INSTALLTION_PATH.clone()];
     }
 
     let json_obj: Value = from_str(&setting_content).unwrap();
 
     if json_obj.is_null() || !json_obj.is_object() {
-        return DEFAULT_INSTALLTION_PATH.clone();
+        return vec![DEFAULT_INSTALLTION_PATH.clone()];
     }
 
-    if json_obj["directory"].is_null() || !json_obj["directory"].is_string() {
-        return DEFAULT_INSTALLTION_PATH.clone();
+    match &json_obj["directory"] {
+        Value::String(directory) => vec![PathBuf::from(directory)],
+        Value::Array(directories) => {
+            let roots = directories
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(PathBuf::from)
+                .collect::<Vec<_>>();
+
+            if roots.is_empty() {
+                vec![DEFAULT_INSTALLTION_PATH.clone()]
+            } else {
+                roots
+            }
+        }
+        _ => vec![DEFAULT_INSTALLTION_PATH.clone()],
     }
-
-    let directory = json_obj["directory"].as_str().unwrap();
-
-    PathBuf::from(directory)
 }
 
 fn get_default_installtion_path() -> PathBuf {
@@ -131,29 +165,161 @@ fn get_default_installtion_path() -> PathBuf {
     default_path
 }
 
+// Name of the env var that overrides project/default version resolution for
+// a single invocation, set either directly or via the `--use-version` flag.
+pub const USE_VERSION_ENV: &str = "NVMD_USE_VERSION";
+
+// Where the resolved `VERSION` came from, so diagnostics (see `info.rs`) can
+// explain *why* a given version is active instead of just what it is.
+pub enum VersionSource {
+    Override,
+    ProjectFile(PathBuf),
+    DefaultFile,
+    None,
+}
+
 fn get_version() -> String {
-    let mut nvmdrc = match env::current_dir() {
-        Err(_) => PathBuf::from(""),
-        Ok(dir) => dir,
-    };
-    nvmdrc.push(".nvmdrc");
+    resolve_version().0
+}
 
-    let project_version = match read_to_string(&nvmdrc) {
-        Err(_) => String::from(""),
-        Ok(v) => v,
-    };
+pub(crate) fn resolve_version() -> (String, VersionSource) {
+    if let Ok(use_version) = env::var(USE_VERSION_ENV) {
+        if !use_version.is_empty() {
+            return (resolve_version_spec(use_version.trim()), VersionSource::Override);
+        }
+    }
 
-    if !project_version.is_empty() {
-        return project_version;
+    if let Some(nvmdrc) = find_nvmdrc() {
+        let project_version = match read_to_string(&nvmdrc) {
+            Err(_) => String::from(""),
+            Ok(v) => v,
+        };
+
+        if !project_version.is_empty() {
+            return (
+                resolve_version_spec(project_version.trim()),
+                VersionSource::ProjectFile(nvmdrc),
+            );
+        }
     }
 
     let mut default_path = NVMD_PATH.clone();
     default_path.push("default");
 
-    match read_to_string(&default_path) {
+    let default_version = match read_to_string(&default_path) {
         Err(_) => String::from(""),
         Ok(v) => v,
+    };
+
+    if default_version.is_empty() {
+        return (default_version, VersionSource::None);
+    }
+
+    (resolve_version_spec(default_version.trim()), VersionSource::DefaultFile)
+}
+
+// Walk upward from the current directory looking for `.nvmdrc`, the same way
+// project-scoped tool configs are resolved, so that running a command from a
+// subdirectory of a project still picks up the repo-root version pin.
+// Stops at (and includes) the user's home directory, or the filesystem root.
+fn find_nvmdrc() -> Option<PathBuf> {
+    let mut dir = env::current_dir().ok()?;
+    let home = dirs::home_dir();
+
+    loop {
+        let candidate = dir.join(".nvmdrc");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+
+        if home.as_deref() == Some(dir.as_path()) {
+            return None;
+        }
+
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+// Resolve a `.nvmdrc` / `default` spec (an exact version, a semver range like
+// `^18.2`, a bare major like `18`, or `lts/*`) to the exact installed version
+// string that satisfies it. A spec that already names an installed version
+// directory is returned as is, before any range matching is tried; an empty
+// string is returned when nothing matches.
+// Installation roots are searched in order; the first root containing a match
+// wins, mirroring how multi-entry search paths resolve elsewhere.
+fn resolve_version_spec(spec: &str) -> String {
+    for root in INSTALLTION_PATH.iter() {
+        if root.join(spec).is_dir() {
+            return String::from(spec);
+        }
+    }
+
+    let req = match version_req_from_spec(spec) {
+        Some(req) => req,
+        None => return String::from(""),
+    };
+
+    for root in INSTALLTION_PATH.iter() {
+        if let Some(name) = best_matching_version(root, &req) {
+            return name;
+        }
+    }
+
+    String::from("")
+}
+
+fn best_matching_version(root: &Path, req: &VersionReq) -> Option<String> {
+    let entries = root.read_dir().ok()?;
+
+    let mut best: Option<(Version, String)> = None;
+    for entry in entries.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        let version = match Version::parse(name.trim_start_matches('v')) {
+            Err(_) => continue,
+            Ok(version) => version,
+        };
+
+        if !req.matches(&version) {
+            continue;
+        }
+
+        if best.as_ref().is_none_or(|(best_version, _)| version > *best_version) {
+            best = Some((version, name));
+        }
     }
+
+    best.map(|(_, name)| name)
+}
+
+fn version_req_from_spec(spec: &str) -> Option<VersionReq> {
+    if spec == "lts/*" {
+        return VersionReq::parse("*").ok();
+    }
+
+    // Named codenames (`lts/gallium`, `lts/hydrogen`, ...) pin a specific
+    // major, but we have no codename -> major table here. Resolving to "*"
+    // would silently hand back an unrelated, newer major; degrade to no
+    // match instead so an empty `VERSION` makes the problem visible.
+    if spec.starts_with("lts/") {
+        return None;
+    }
+
+    if spec.chars().all(|c| c.is_ascii_digit()) {
+        return VersionReq::parse(&format!("^{}", spec)).ok();
+    }
+
+    // The `semver` crate requires comma-separated comparators (`>=16, <19`),
+    // but users write space-separated ranges (`>=16 <19`) as other node
+    // version managers accept. Normalize before parsing.
+    let normalized = spec.split_whitespace().collect::<Vec<_>>().join(", ");
+
+    VersionReq::parse(&normalized).ok()
 }
 
 fn get_nvmd_path() -> PathBuf {
@@ -169,6 +335,23 @@ fn default_home_dir() -> Result<PathBuf, ErrorKind> {
     Ok(home)
 }
 
+// Look for a `--use-version <spec>` flag among the process args and, if
+// present, export it as `NVMD_USE_VERSION` so the `VERSION` lazy_static (and
+// everything derived from it, like `ENV_PATH`/`BINARY_ENV_PATH`/`get_bin_path`)
+// picks up the override. Entrypoints that accept arg-based overrides should
+// call this before `VERSION` is first dereferenced.
+pub fn apply_use_version_flag() {
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--use-version" {
+            if let Some(spec) = args.next() {
+                env::set_var(USE_VERSION_ENV, spec);
+            }
+            return;
+        }
+    }
+}
+
 pub enum Error {
     Message(String),
     Code(i32),
@@ -193,3 +376,34 @@ impl IntoResult<()> for Result<ExitStatus, String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_space_separated_range() {
+        let req = version_req_from_spec(">=16 <19").expect("range should parse");
+        assert!(req.matches(&Version::parse("18.2.0").unwrap()));
+        assert!(!req.matches(&Version::parse("19.0.0").unwrap()));
+    }
+
+    #[test]
+    fn maps_bare_major_to_caret_range() {
+        let req = version_req_from_spec("18").expect("bare major should parse");
+        assert!(req.matches(&Version::parse("18.2.0").unwrap()));
+        assert!(!req.matches(&Version::parse("19.0.0").unwrap()));
+    }
+
+    #[test]
+    fn maps_lts_star_to_any_version() {
+        let req = version_req_from_spec("lts/*").expect("lts/* should parse");
+        assert!(req.matches(&Version::parse("12.0.0").unwrap()));
+        assert!(req.matches(&Version::parse("20.0.0").unwrap()));
+    }
+
+    #[test]
+    fn named_lts_codename_has_no_match_instead_of_any_version() {
+        assert!(version_req_from_spec("lts/gallium").is_none());
+    }
+}
@@ -0,0 +1,71 @@
+// `info` command: prints the resolved VERSION/ENV_PATH/NPM_PREFIX etc.
+
+use std::path::{Path, PathBuf};
+
+use crate::common::{self, VersionSource, BINARY_ENV_PATH, ENV_PATH, INSTALLTION_PATH, NPM_PREFIX, NVMD_PATH};
+
+pub fn print_info() {
+    println!("NVMD_PATH: {}", display_path(&NVMD_PATH));
+
+    let (version, source) = common::resolve_version();
+    println!("VERSION: {}", display_value(&version));
+    println!("  resolved via: {}", describe_source(&source));
+
+    println!("INSTALLTION_PATH:");
+    for root in INSTALLTION_PATH.iter() {
+        println!("  {}", display_path(root));
+    }
+
+    if version.is_empty() {
+        println!("  bin dir: (unknown, no version resolved)");
+    } else {
+        let bin_dir = PathBuf::from(common::get_bin_path());
+        println!(
+            "  bin dir: {} ({})",
+            display_path(&bin_dir),
+            if bin_dir.exists() { "exists" } else { "missing" }
+        );
+    }
+
+    println!("NPM_PREFIX: {}", display_path(&NPM_PREFIX));
+    if NPM_PREFIX.as_os_str().is_empty() {
+        println!("  warning: `npm config get prefix` could not be run or returned no existing directory");
+    }
+
+    print_path_preview("ENV_PATH", &ENV_PATH);
+    print_path_preview("BINARY_ENV_PATH", &BINARY_ENV_PATH);
+}
+
+fn describe_source(source: &VersionSource) -> String {
+    match source {
+        VersionSource::Override => format!("{} override", common::USE_VERSION_ENV),
+        VersionSource::ProjectFile(path) => format!(".nvmdrc ({})", display_path(path)),
+        VersionSource::DefaultFile => String::from("default file"),
+        VersionSource::None => String::from("nothing (no .nvmdrc, no default)"),
+    }
+}
+
+fn print_path_preview(label: &str, value: &std::ffi::OsString) {
+    if value.is_empty() {
+        println!("{}: (empty)", label);
+        return;
+    }
+
+    let entries = std::env::split_paths(value).take(3).collect::<Vec<_>>();
+    println!("{}:", label);
+    for entry in entries {
+        println!("  {}", display_path(&entry));
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    display_value(&path.to_string_lossy())
+}
+
+fn display_value(value: &str) -> String {
+    if value.is_empty() {
+        String::from("(empty)")
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,175 @@
+// Wrapper scripts for the active version's binaries, so nvmd doesn't require
+// ENV_PATH/BINARY_ENV_PATH to be on the caller's PATH.
+
+use std::{
+    ffi::OsString,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::common::{self, NVMD_PATH};
+
+// Which directory a collected executable resolves through, so its shim `exec`s
+// via the same directory it was found in rather than always NPM_PREFIX/bin.
+#[derive(Clone, Copy)]
+enum ShimTarget {
+    Bin,
+    Binary,
+}
+
+impl ShimTarget {
+    fn dir(self) -> OsString {
+        match self {
+            ShimTarget::Bin => common::get_bin_path(),
+            ShimTarget::Binary => common::get_binary_bin_path(),
+        }
+    }
+
+    // Resolved directory, but only if it's an existing absolute path. A bare
+    // relative path (e.g. `"bin"`, which is what `get_binary_bin_path()`
+    // degenerates to when `NPM_PREFIX` is empty) must never be baked into a
+    // shim: it would resolve against whatever directory the shim is later run
+    // from, rather than the intended install location.
+    fn existing_absolute_dir(self) -> Option<PathBuf> {
+        let dir = PathBuf::from(self.dir());
+        if dir.is_absolute() && dir.is_dir() {
+            Some(dir)
+        } else {
+            None
+        }
+    }
+}
+
+// Stable directory that shims are written into. Callers put this on `PATH`
+// once; the directory itself does not change across `nvmd use`/version
+// switches, but each shim holds the bin directory of the version that was
+// active when it was written, so `regenerate_shims` has to run again after a
+// switch.
+fn shim_dir() -> PathBuf {
+    let mut dir = NVMD_PATH.clone();
+    dir.push("shim");
+    dir
+}
+
+// Regenerate every shim for the currently active version, then prune shims
+// left over from a binary that no longer exists (e.g. after switching the
+// default version or uninstalling a package-installed CLI).
+pub fn regenerate_shims() -> io::Result<()> {
+    let dir = shim_dir();
+    fs::create_dir_all(&dir)?;
+
+    let mut current = Vec::new();
+    for target in [ShimTarget::Bin, ShimTarget::Binary] {
+        if let Some(bin_dir) = target.existing_absolute_dir() {
+            current.extend(collect_executables(&bin_dir).into_iter().map(|name| (name, target)));
+        }
+    }
+
+    for (name, target) in &current {
+        write_shim(&dir, name, *target)?;
+    }
+
+    let names = current.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+    prune_stale_shims(&dir, &names)?;
+
+    Ok(())
+}
+
+fn collect_executables(bin_dir: &Path) -> Vec<String> {
+    let entries = match fs::read_dir(bin_dir) {
+        Err(_) => return Vec::new(),
+        Ok(entries) => entries,
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| is_executable(&entry.path()))
+        .filter_map(|entry| shim_name(&entry.path()))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.is_file()
+        && fs::metadata(path)
+            .map(|meta| meta.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("exe") | Some("cmd") | Some("bat") | Some("ps1")
+        )
+}
+
+fn shim_name(path: &Path) -> Option<String> {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+}
+
+#[cfg(unix)]
+fn write_shim(dir: &Path, name: &str, target: ShimTarget) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let shim_path = dir.join(name);
+    let script = format!(
+        "#!/bin/sh\nexport PATH=\"{}:$PATH\"\nexec \"{}\" \"$@\"\n",
+        target.dir().to_string_lossy(),
+        name,
+    );
+
+    let mut file = fs::File::create(&shim_path)?;
+    file.write_all(script.as_bytes())?;
+
+    let mut perms = file.metadata()?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&shim_path, perms)?;
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn write_shim(dir: &Path, name: &str, target: ShimTarget) -> io::Result<()> {
+    let shim_path = dir.join(format!("{}.cmd", name));
+    let script = format!(
+        "@echo off\r\nset PATH={};%PATH%\r\n\"{}\" %*\r\n",
+        target.dir().to_string_lossy(),
+        name,
+    );
+
+    fs::write(shim_path, script)
+}
+
+#[cfg(unix)]
+fn prune_stale_shims(dir: &Path, current: &[String]) -> io::Result<()> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !current.contains(&file_name) {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn prune_stale_shims(dir: &Path, current: &[String]) -> io::Result<()> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let stale = match shim_name(&entry.path()) {
+            Some(name) => !current.contains(&name),
+            None => true,
+        };
+
+        if stale {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}